@@ -29,6 +29,11 @@ impl Engine {
                 (*id, renderer)
             })
             .collect::<HashMap<_, _>>();
+
+        for window in windows.values() {
+            window.request_redraw();
+        }
+
         Ok(Self {
             renderers,
             windows,
@@ -51,7 +56,25 @@ impl Engine {
                     self.renderers.remove(&window_id);
                 }
             }
-            // TODO: handle resize / input / redraw
+            WindowEvent::Resized(_) => {
+                if let Some(renderer) = self.renderers.get_mut(&window_id) {
+                    renderer.resize();
+                }
+                if let Some(window) = self.windows.get(&window_id) {
+                    window.request_redraw();
+                }
+            }
+            WindowEvent::RedrawRequested => {
+                if let (Some(renderer), Some(window)) = (
+                    self.renderers.get_mut(&window_id),
+                    self.windows.get(&window_id),
+                ) {
+                    if let Err(error) = renderer.render(window) {
+                        tracing::error!("Failed to render to window `{:?}`: {}", window_id, error);
+                    }
+                    window.request_redraw();
+                }
+            }
             _ => {}
         }
     }
@@ -65,9 +88,11 @@ impl Engine {
         let window_id = window.id();
         self.windows.insert(window_id, window.clone());
 
-        let renderer = Renderer::new(window)?;
+        let renderer = Renderer::new(window.clone())?;
         self.renderers.insert(window_id, renderer);
 
+        window.request_redraw();
+
         Ok(window_id)
     }
 }
@@ -0,0 +1,55 @@
+use anyhow::{Result, anyhow};
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk;
+
+/// Allocates a buffer and binds it to freshly allocated device memory of a
+/// type satisfying both `usage`'s memory requirements and `properties`.
+pub unsafe fn create_buffer(
+    instance: &Instance,
+    device: &Device,
+    physical_device: vk::PhysicalDevice,
+    size: vk::DeviceSize,
+    usage: vk::BufferUsageFlags,
+    properties: vk::MemoryPropertyFlags,
+) -> Result<(vk::Buffer, vk::DeviceMemory)> {
+    let buffer_info = vk::BufferCreateInfo::builder()
+        .size(size)
+        .usage(usage)
+        .sharing_mode(vk::SharingMode::EXCLUSIVE);
+
+    let buffer = device.create_buffer(&buffer_info, None)?;
+    let requirements = device.get_buffer_memory_requirements(buffer);
+
+    let memory_info = vk::MemoryAllocateInfo::builder()
+        .allocation_size(requirements.size)
+        .memory_type_index(get_memory_type_index(
+            instance,
+            physical_device,
+            properties,
+            requirements,
+        )?);
+
+    let memory = device.allocate_memory(&memory_info, None)?;
+    device.bind_buffer_memory(buffer, memory, 0)?;
+
+    Ok((buffer, memory))
+}
+
+unsafe fn get_memory_type_index(
+    instance: &Instance,
+    physical_device: vk::PhysicalDevice,
+    properties: vk::MemoryPropertyFlags,
+    requirements: vk::MemoryRequirements,
+) -> Result<u32> {
+    let memory = instance.get_physical_device_memory_properties(physical_device);
+
+    (0..memory.memory_type_count)
+        .find(|&i| {
+            let suitable = requirements.memory_type_bits & (1 << i) != 0;
+            let sufficient = memory.memory_types[i as usize]
+                .property_flags
+                .contains(properties);
+            suitable && sufficient
+        })
+        .ok_or_else(|| anyhow!("Failed to find suitable memory type."))
+}
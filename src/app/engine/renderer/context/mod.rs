@@ -11,18 +11,30 @@ use vulkanalia::window as vk_window;
 use winit::window::Window;
 
 use vulkanalia::vk::ExtDebugUtilsExtension;
+use vulkanalia::vk::KhrSurfaceExtension;
+use vulkanalia::vk::KhrSwapchainExtension;
+
+use super::frame::Frame;
+use super::pipeline::Pipeline;
+use super::swapchain::{Swapchain, SwapchainSupport};
 
 pub struct Context {
     pub entry: Entry,
     pub instance: Instance,
     pub data: AppData,
     pub device: Device,
+    pub swapchain: Swapchain,
+    pub pipeline: Pipeline,
+    pub frame: Frame,
 }
 
 pub struct AppData {
     pub messenger: vk::DebugUtilsMessengerEXT,
+    pub surface: vk::SurfaceKHR,
+    debug_user_data: *mut c_void,
     physical_device: vk::PhysicalDevice,
     graphics_queue: vk::Queue,
+    present_queue: vk::Queue,
 }
 
 const VALIDATION_ENABLED: bool = cfg!(debug_assertions);
@@ -32,24 +44,69 @@ const VALIDATION_LAYER: vk::ExtensionName =
 
 const PORTABILITY_MACOS_VERSION: Version = Version::new(1, 3, 216);
 
+const REQUIRED_DEVICE_EXTENSIONS: &[vk::ExtensionName] = &[vk::KHR_SWAPCHAIN_EXTENSION.name];
+
+/// Identifies the active validation layer build so `Context::debug_callback`
+/// can tell known-buggy layer versions apart from real validation errors.
+struct DebugCallbackUserData {
+    layer_name: vk::ExtensionName,
+    layer_spec_version: u32,
+}
+
+/// `VUID-vkCmdEndDebugUtilsLabelEXT-commandBuffer-01912`: some Khronos
+/// validation layer builds wrongly flag debug labels that start and end in
+/// different command buffers.
+const SPURIOUS_LABEL_MESSAGE_ID: i32 = 0x56146426u32 as i32;
+const SPURIOUS_LABEL_LAYER_MIN_VERSION: u32 = vk::make_version(1, 3, 240);
+const SPURIOUS_LABEL_LAYER_MAX_VERSION: u32 = vk::make_version(1, 3, 250);
+
 impl Context {
     pub unsafe fn create(window: &Window) -> Result<Self> {
         let loader = LibloadingLoader::new(LIBRARY)?;
         let mut data = AppData {
             messenger: vk::DebugUtilsMessengerEXT::null(),
+            surface: vk::SurfaceKHR::null(),
+            debug_user_data: std::ptr::null_mut(),
             physical_device: vk::PhysicalDevice::null(),
             graphics_queue: vk::Queue::null(),
+            present_queue: vk::Queue::null(),
         };
         let entry = Entry::new(loader).map_err(|e| anyhow!("{}", e))?;
         let instance = Self::create_instance(window, &entry, &mut data)?;
+        data.surface = vk_window::create_surface(&instance, window, window)?;
         Self::pick_physical_device(&instance, &mut data)?;
 
         let device = Self::create_logical_device(&entry, &instance, &mut data)?;
+
+        let indices = QueueFamilyIndices::get(&instance, &data, data.physical_device)?;
+        let swapchain = Swapchain::create(
+            window,
+            &instance,
+            &device,
+            data.surface,
+            data.physical_device,
+            indices.graphics,
+            indices.present,
+        )?;
+        let pipeline = Pipeline::create(&device, swapchain.format, swapchain.extent)?;
+        let frame = Frame::create(
+            &instance,
+            &device,
+            data.physical_device,
+            indices.graphics,
+            pipeline.render_pass,
+            pipeline.pipeline,
+            &swapchain,
+        )?;
+
         Ok(Self {
             entry,
             instance,
             data,
             device,
+            swapchain,
+            pipeline,
+            frame,
         })
     }
 
@@ -65,8 +122,8 @@ impl Context {
             .engine_version(vk::make_version(1, 0, 0))
             .api_version(vk::make_version(1, 3, 0)); // Vulkan 1.3
 
-        let available_layers = entry
-            .enumerate_instance_layer_properties()?
+        let layer_properties = entry.enumerate_instance_layer_properties()?;
+        let available_layers = layer_properties
             .iter()
             .map(|l| l.layer_name)
             .collect::<HashSet<_>>();
@@ -81,6 +138,21 @@ impl Context {
             return Err(anyhow!("Validation layer requested but not supported."));
         }
 
+        if VALIDATION_ENABLED {
+            let validation_layer = layer_properties
+                .iter()
+                .find(|l| l.layer_name == VALIDATION_LAYER);
+            let layer_name = validation_layer
+                .map(|l| l.layer_name)
+                .unwrap_or(VALIDATION_LAYER);
+            let layer_spec_version = validation_layer.map(|l| l.spec_version).unwrap_or(0);
+
+            data.debug_user_data = Box::into_raw(Box::new(DebugCallbackUserData {
+                layer_name,
+                layer_spec_version,
+            })) as *mut c_void;
+        }
+
         let layers = if VALIDATION_ENABLED {
             vec![VALIDATION_LAYER.as_ptr()]
         } else {
@@ -128,7 +200,8 @@ impl Context {
                     | vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION
                     | vk::DebugUtilsMessageTypeFlagsEXT::PERFORMANCE,
             )
-            .user_callback(Some(Self::debug_callback));
+            .user_callback(Some(Self::debug_callback))
+            .user_data(data.debug_user_data);
 
         if VALIDATION_ENABLED {
             info = info.push_next(&mut debug_info);
@@ -144,7 +217,8 @@ impl Context {
                         | vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION
                         | vk::DebugUtilsMessageTypeFlagsEXT::PERFORMANCE,
                 )
-                .user_callback(Some(Self::debug_callback));
+                .user_callback(Some(Self::debug_callback))
+                .user_data(data.debug_user_data);
 
             data.messenger = instance.create_debug_utils_messenger_ext(&debug_info, None)?;
         }
@@ -155,10 +229,31 @@ impl Context {
     pub extern "system" fn debug_callback(
         severity: vk::DebugUtilsMessageSeverityFlagsEXT,
         type_: vk::DebugUtilsMessageTypeFlagsEXT,
-        data: *const vk::DebugUtilsMessengerCallbackDataEXT,
-        _: *mut c_void,
+        callback_data: *const vk::DebugUtilsMessengerCallbackDataEXT,
+        user_data: *mut c_void,
     ) -> vk::Bool32 {
-        let data = unsafe { *data };
+        // The pointer and any state it refers to may be invalid mid-unwind.
+        if std::thread::panicking() {
+            return vk::FALSE;
+        }
+
+        let data = unsafe { *callback_data };
+
+        if data.message_id_number == SPURIOUS_LABEL_MESSAGE_ID
+            && Self::is_known_spurious_validation_layer(user_data)
+        {
+            if let Some(info) = unsafe { (user_data as *const DebugCallbackUserData).as_ref() } {
+                let name = unsafe { CStr::from_ptr(info.layer_name.as_ptr()) }.to_string_lossy();
+                tracing::trace!(
+                    "Suppressing known-spurious VUID {:#x} from {} v{}",
+                    SPURIOUS_LABEL_MESSAGE_ID,
+                    name,
+                    info.layer_spec_version
+                );
+            }
+            return vk::FALSE;
+        }
+
         let message = unsafe { CStr::from_ptr(data.message) }.to_string_lossy();
 
         if severity >= vk::DebugUtilsMessageSeverityFlagsEXT::ERROR {
@@ -174,14 +269,129 @@ impl Context {
         vk::FALSE
     }
 
+    /// Whether `user_data` points at a `DebugCallbackUserData` reporting a
+    /// Khronos validation layer build known to misreport debug labels that
+    /// span command buffers (see `SPURIOUS_LABEL_MESSAGE_ID`).
+    fn is_known_spurious_validation_layer(user_data: *mut c_void) -> bool {
+        let Some(data) = (unsafe { (user_data as *const DebugCallbackUserData).as_ref() }) else {
+            return false;
+        };
+
+        (SPURIOUS_LABEL_LAYER_MIN_VERSION..=SPURIOUS_LABEL_LAYER_MAX_VERSION)
+            .contains(&data.layer_spec_version)
+    }
+
+    pub unsafe fn recreate_swapchain(&mut self, window: &Window) -> Result<()> {
+        self.device.device_wait_idle()?;
+
+        self.frame.destroy_swapchain_resources(&self.device);
+        self.pipeline.destroy(&self.device);
+        self.swapchain.destroy(&self.device);
+
+        let indices =
+            QueueFamilyIndices::get(&self.instance, &self.data, self.data.physical_device)?;
+        self.swapchain = Swapchain::create(
+            window,
+            &self.instance,
+            &self.device,
+            self.data.surface,
+            self.data.physical_device,
+            indices.graphics,
+            indices.present,
+        )?;
+        self.pipeline =
+            Pipeline::create(&self.device, self.swapchain.format, self.swapchain.extent)?;
+        self.frame.recreate_swapchain_resources(
+            &self.device,
+            self.pipeline.render_pass,
+            self.pipeline.pipeline,
+            &self.swapchain,
+        )?;
+
+        Ok(())
+    }
+
+    /// Acquires the next swapchain image, submits its prerecorded command
+    /// buffer, and presents it. Recreates the swapchain in place when it is
+    /// reported out of date or suboptimal instead of surfacing an error.
+    pub unsafe fn render(&mut self, window: &Window) -> Result<()> {
+        self.device
+            .wait_for_fences(&[self.frame.in_flight_fence], true, u64::MAX)?;
+
+        let image_index = match self.device.acquire_next_image_khr(
+            self.swapchain.swapchain,
+            u64::MAX,
+            self.frame.image_available_semaphore,
+            vk::Fence::null(),
+        ) {
+            Ok((image_index, false)) => image_index as usize,
+            Ok((_, true)) => return self.recreate_swapchain(window),
+            Err(vk::ErrorCode::OUT_OF_DATE_KHR) => return self.recreate_swapchain(window),
+            Err(error) => return Err(anyhow!(error)),
+        };
+
+        let image_in_flight = self.frame.images_in_flight[image_index];
+        if !image_in_flight.is_null() {
+            self.device
+                .wait_for_fences(&[image_in_flight], true, u64::MAX)?;
+        }
+        self.frame.images_in_flight[image_index] = self.frame.in_flight_fence;
+
+        let wait_semaphores = &[self.frame.image_available_semaphore];
+        let wait_stages = &[vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT];
+        let command_buffers = &[self.frame.command_buffers[image_index]];
+        let signal_semaphores = &[self.frame.render_finished_semaphore];
+        let submit_info = vk::SubmitInfo::builder()
+            .wait_semaphores(wait_semaphores)
+            .wait_dst_stage_mask(wait_stages)
+            .command_buffers(command_buffers)
+            .signal_semaphores(signal_semaphores);
+
+        self.device.reset_fences(&[self.frame.in_flight_fence])?;
+        self.device.queue_submit(
+            self.data.graphics_queue,
+            &[submit_info],
+            self.frame.in_flight_fence,
+        )?;
+
+        let swapchains = &[self.swapchain.swapchain];
+        let image_indices = &[image_index as u32];
+        let present_info = vk::PresentInfoKHR::builder()
+            .wait_semaphores(signal_semaphores)
+            .swapchains(swapchains)
+            .image_indices(image_indices);
+
+        let result = self
+            .device
+            .queue_present_khr(self.data.present_queue, &present_info);
+        let swapchain_stale = matches!(result, Ok(true) | Err(vk::ErrorCode::OUT_OF_DATE_KHR));
+
+        if swapchain_stale {
+            self.recreate_swapchain(window)?;
+        } else {
+            result.map_err(|error| anyhow!(error))?;
+        }
+
+        Ok(())
+    }
+
     unsafe fn destroy(&mut self) {
+        self.frame.destroy(&self.device);
+        self.pipeline.destroy(&self.device);
+        self.swapchain.destroy(&self.device);
+        self.device.destroy_device(None);
+
         if VALIDATION_ENABLED {
             self.instance
                 .destroy_debug_utils_messenger_ext(self.data.messenger, None);
         }
 
+        self.instance.destroy_surface_khr(self.data.surface, None);
         self.instance.destroy_instance(None);
-        self.device.destroy_device(None);
+
+        if !self.data.debug_user_data.is_null() {
+            drop(unsafe { Box::from_raw(self.data.debug_user_data as *mut DebugCallbackUserData) });
+        }
     }
 
     unsafe fn check_physical_device(
@@ -190,10 +400,59 @@ impl Context {
         physical_device: vk::PhysicalDevice,
     ) -> Result<()> {
         QueueFamilyIndices::get(instance, data, physical_device)?;
+        Self::check_physical_device_extensions(instance, physical_device)?;
+
+        let support = SwapchainSupport::get(instance, data.surface, physical_device)?;
+        if support.formats.is_empty() || support.present_modes.is_empty() {
+            return Err(anyhow!(SuitabilityError("Insufficient swapchain support.")));
+        }
+
         Ok(())
     }
 
+    unsafe fn check_physical_device_extensions(
+        instance: &Instance,
+        physical_device: vk::PhysicalDevice,
+    ) -> Result<()> {
+        let extensions = instance
+            .enumerate_device_extension_properties(physical_device, None)?
+            .iter()
+            .map(|e| e.extension_name)
+            .collect::<HashSet<_>>();
+
+        if REQUIRED_DEVICE_EXTENSIONS
+            .iter()
+            .all(|e| extensions.contains(e))
+        {
+            Ok(())
+        } else {
+            Err(anyhow!(SuitabilityError(
+                "Missing required device extension."
+            )))
+        }
+    }
+
+    /// Scores a physical device's suitability; higher is better. The score is
+    /// the maximum supported 2D image dimension plus 1000 for a discrete GPU,
+    /// so a non-discrete device whose maximum dimension is more than 1000
+    /// larger still outscores a discrete one.
+    unsafe fn score_physical_device(
+        instance: &Instance,
+        physical_device: vk::PhysicalDevice,
+    ) -> u32 {
+        let properties = instance.get_physical_device_properties(physical_device);
+
+        let mut score = properties.limits.max_image_dimension2_d;
+        if properties.device_type == vk::PhysicalDeviceType::DISCRETE_GPU {
+            score += 1000;
+        }
+
+        score
+    }
+
     unsafe fn pick_physical_device(instance: &Instance, data: &mut AppData) -> Result<()> {
+        let mut best = None;
+
         for physical_device in instance.enumerate_physical_devices()? {
             let properties = instance.get_physical_device_properties(physical_device);
 
@@ -203,14 +462,28 @@ impl Context {
                     properties.device_name,
                     error
                 );
-            } else {
+                continue;
+            }
+
+            let score = Self::score_physical_device(instance, physical_device);
+            let is_better = match best {
+                Some((_, best_score)) => score > best_score,
+                None => true,
+            };
+            if is_better {
+                best = Some((physical_device, score));
+            }
+        }
+
+        match best {
+            Some((physical_device, _)) => {
+                let properties = instance.get_physical_device_properties(physical_device);
                 tracing::info!("Selected physical device (`{}`).", properties.device_name);
                 data.physical_device = physical_device;
-                return Ok(());
+                Ok(())
             }
+            None => Err(anyhow!("Failed to find suitable physical device.")),
         }
-
-        Err(anyhow!("Failed to find suitable physical device."))
     }
 
     unsafe fn create_logical_device(
@@ -220,10 +493,16 @@ impl Context {
     ) -> Result<Device> {
         let indices = QueueFamilyIndices::get(instance, data, data.physical_device)?;
 
+        let unique_indices = HashSet::from([indices.graphics, indices.present]);
         let queue_priorities = &[1.0];
-        let queue_info = vk::DeviceQueueCreateInfo::builder()
-            .queue_family_index(indices.graphics)
-            .queue_priorities(queue_priorities);
+        let queue_infos = unique_indices
+            .iter()
+            .map(|&index| {
+                vk::DeviceQueueCreateInfo::builder()
+                    .queue_family_index(index)
+                    .queue_priorities(queue_priorities)
+            })
+            .collect::<Vec<_>>();
 
         let layers = if VALIDATION_ENABLED {
             vec![VALIDATION_LAYER.as_ptr()]
@@ -231,7 +510,10 @@ impl Context {
             vec![]
         };
 
-        let mut extensions = vec![];
+        let mut extensions = REQUIRED_DEVICE_EXTENSIONS
+            .iter()
+            .map(|e| e.as_ptr())
+            .collect::<Vec<_>>();
 
         // Required by Vulkan SDK on macOS since 1.3.216.
         if cfg!(target_os = "macos") && entry.version()? >= PORTABILITY_MACOS_VERSION {
@@ -239,19 +521,25 @@ impl Context {
         }
         let features = vk::PhysicalDeviceFeatures::builder();
 
-        let queue_infos = &[queue_info];
         let info = vk::DeviceCreateInfo::builder()
-            .queue_create_infos(queue_infos)
+            .queue_create_infos(&queue_infos)
             .enabled_layer_names(&layers)
             .enabled_extension_names(&extensions)
             .enabled_features(&features);
 
         let device = instance.create_device(data.physical_device, &info, None)?;
         data.graphics_queue = device.get_device_queue(indices.graphics, 0);
+        data.present_queue = device.get_device_queue(indices.present, 0);
         Ok(device)
     }
 }
 
+impl Drop for Context {
+    fn drop(&mut self) {
+        unsafe { self.destroy() };
+    }
+}
+
 #[derive(Debug, Error)]
 #[error("Missing {0}.")]
 pub struct SuitabilityError(pub &'static str);
@@ -259,6 +547,7 @@ pub struct SuitabilityError(pub &'static str);
 #[derive(Copy, Clone, Debug)]
 struct QueueFamilyIndices {
     graphics: u32,
+    present: u32,
 }
 
 impl QueueFamilyIndices {
@@ -274,8 +563,20 @@ impl QueueFamilyIndices {
             .position(|p| p.queue_flags.contains(vk::QueueFlags::GRAPHICS))
             .map(|i| i as u32);
 
-        if let Some(graphics) = graphics {
-            Ok(Self { graphics })
+        let mut present = None;
+        for (index, _) in properties.iter().enumerate() {
+            if instance.get_physical_device_surface_support_khr(
+                physical_device,
+                index as u32,
+                data.surface,
+            )? {
+                present = Some(index as u32);
+                break;
+            }
+        }
+
+        if let (Some(graphics), Some(present)) = (graphics, present) {
+            Ok(Self { graphics, present })
         } else {
             Err(anyhow!(SuitabilityError(
                 "Missing required queue families."
@@ -0,0 +1,239 @@
+use std::mem::size_of;
+
+use anyhow::Result;
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk;
+
+use super::buffer::create_buffer;
+use super::swapchain::Swapchain;
+use super::vertex::{VERTICES, Vertex};
+
+/// Per-swapchain command buffers/framebuffers plus the persistent vertex
+/// buffer and frame synchronisation primitives. The framebuffers, command
+/// buffers and `images_in_flight` are rebuilt whenever the swapchain is (see
+/// `recreate_swapchain_resources`); the rest outlive swapchain recreation.
+pub struct Frame {
+    pub command_pool: vk::CommandPool,
+    pub command_buffers: Vec<vk::CommandBuffer>,
+    pub framebuffers: Vec<vk::Framebuffer>,
+    pub vertex_buffer: vk::Buffer,
+    pub vertex_buffer_memory: vk::DeviceMemory,
+    pub image_available_semaphore: vk::Semaphore,
+    pub render_finished_semaphore: vk::Semaphore,
+    pub in_flight_fence: vk::Fence,
+    pub images_in_flight: Vec<vk::Fence>,
+}
+
+impl Frame {
+    pub unsafe fn create(
+        instance: &Instance,
+        device: &Device,
+        physical_device: vk::PhysicalDevice,
+        graphics_queue_family: u32,
+        render_pass: vk::RenderPass,
+        pipeline: vk::Pipeline,
+        swapchain: &Swapchain,
+    ) -> Result<Self> {
+        let command_pool = Self::create_command_pool(device, graphics_queue_family)?;
+        let (vertex_buffer, vertex_buffer_memory) =
+            Self::create_vertex_buffer(instance, device, physical_device)?;
+        let framebuffers = Self::create_framebuffers(device, render_pass, swapchain)?;
+        let command_buffers = Self::create_command_buffers(
+            device,
+            command_pool,
+            render_pass,
+            pipeline,
+            vertex_buffer,
+            swapchain,
+            &framebuffers,
+        )?;
+        let (image_available_semaphore, render_finished_semaphore, in_flight_fence) =
+            Self::create_sync_objects(device)?;
+        let images_in_flight = swapchain.images.iter().map(|_| vk::Fence::null()).collect();
+
+        Ok(Self {
+            command_pool,
+            command_buffers,
+            framebuffers,
+            vertex_buffer,
+            vertex_buffer_memory,
+            image_available_semaphore,
+            render_finished_semaphore,
+            in_flight_fence,
+            images_in_flight,
+        })
+    }
+
+    unsafe fn create_command_pool(
+        device: &Device,
+        graphics_queue_family: u32,
+    ) -> Result<vk::CommandPool> {
+        let info = vk::CommandPoolCreateInfo::builder()
+            .flags(vk::CommandPoolCreateFlags::empty())
+            .queue_family_index(graphics_queue_family);
+
+        Ok(device.create_command_pool(&info, None)?)
+    }
+
+    unsafe fn create_vertex_buffer(
+        instance: &Instance,
+        device: &Device,
+        physical_device: vk::PhysicalDevice,
+    ) -> Result<(vk::Buffer, vk::DeviceMemory)> {
+        let size = (size_of::<Vertex>() * VERTICES.len()) as vk::DeviceSize;
+
+        let (vertex_buffer, vertex_buffer_memory) = create_buffer(
+            instance,
+            device,
+            physical_device,
+            size,
+            vk::BufferUsageFlags::VERTEX_BUFFER,
+            vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+        )?;
+
+        let memory =
+            device.map_memory(vertex_buffer_memory, 0, size, vk::MemoryMapFlags::empty())?;
+        std::ptr::copy_nonoverlapping(VERTICES.as_ptr(), memory.cast(), VERTICES.len());
+        device.unmap_memory(vertex_buffer_memory);
+
+        Ok((vertex_buffer, vertex_buffer_memory))
+    }
+
+    unsafe fn create_framebuffers(
+        device: &Device,
+        render_pass: vk::RenderPass,
+        swapchain: &Swapchain,
+    ) -> Result<Vec<vk::Framebuffer>> {
+        swapchain
+            .image_views
+            .iter()
+            .map(|view| {
+                let attachments = &[*view];
+                let info = vk::FramebufferCreateInfo::builder()
+                    .render_pass(render_pass)
+                    .attachments(attachments)
+                    .width(swapchain.extent.width)
+                    .height(swapchain.extent.height)
+                    .layers(1);
+
+                Ok(device.create_framebuffer(&info, None)?)
+            })
+            .collect()
+    }
+
+    unsafe fn create_command_buffers(
+        device: &Device,
+        command_pool: vk::CommandPool,
+        render_pass: vk::RenderPass,
+        pipeline: vk::Pipeline,
+        vertex_buffer: vk::Buffer,
+        swapchain: &Swapchain,
+        framebuffers: &[vk::Framebuffer],
+    ) -> Result<Vec<vk::CommandBuffer>> {
+        let alloc_info = vk::CommandBufferAllocateInfo::builder()
+            .command_pool(command_pool)
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_buffer_count(framebuffers.len() as u32);
+
+        let command_buffers = device.allocate_command_buffers(&alloc_info)?;
+
+        for (&command_buffer, &framebuffer) in command_buffers.iter().zip(framebuffers) {
+            let begin_info = vk::CommandBufferBeginInfo::builder();
+            device.begin_command_buffer(command_buffer, &begin_info)?;
+
+            let render_area = vk::Rect2D::builder()
+                .offset(vk::Offset2D { x: 0, y: 0 })
+                .extent(swapchain.extent);
+
+            let clear_value = vk::ClearValue {
+                color: vk::ClearColorValue {
+                    float32: [0.0, 0.0, 0.0, 1.0],
+                },
+            };
+            let clear_values = &[clear_value];
+
+            let render_pass_info = vk::RenderPassBeginInfo::builder()
+                .render_pass(render_pass)
+                .framebuffer(framebuffer)
+                .render_area(render_area)
+                .clear_values(clear_values);
+
+            device.cmd_begin_render_pass(
+                command_buffer,
+                &render_pass_info,
+                vk::SubpassContents::INLINE,
+            );
+            device.cmd_bind_pipeline(command_buffer, vk::PipelineBindPoint::GRAPHICS, pipeline);
+            device.cmd_bind_vertex_buffers(command_buffer, 0, &[vertex_buffer], &[0]);
+            device.cmd_draw(command_buffer, VERTICES.len() as u32, 1, 0, 0);
+            device.cmd_end_render_pass(command_buffer);
+
+            device.end_command_buffer(command_buffer)?;
+        }
+
+        Ok(command_buffers)
+    }
+
+    unsafe fn create_sync_objects(
+        device: &Device,
+    ) -> Result<(vk::Semaphore, vk::Semaphore, vk::Fence)> {
+        let semaphore_info = vk::SemaphoreCreateInfo::builder();
+        let fence_info = vk::FenceCreateInfo::builder().flags(vk::FenceCreateFlags::SIGNALED);
+
+        Ok((
+            device.create_semaphore(&semaphore_info, None)?,
+            device.create_semaphore(&semaphore_info, None)?,
+            device.create_fence(&fence_info, None)?,
+        ))
+    }
+
+    /// Tears down the framebuffers and command buffers, which depend on the
+    /// swapchain's image views and extent. The command pool, vertex buffer
+    /// and sync objects are left intact.
+    pub unsafe fn destroy_swapchain_resources(&mut self, device: &Device) {
+        self.framebuffers
+            .iter()
+            .for_each(|framebuffer| device.destroy_framebuffer(*framebuffer, None));
+        self.framebuffers.clear();
+
+        device.free_command_buffers(self.command_pool, &self.command_buffers);
+        self.command_buffers.clear();
+    }
+
+    pub unsafe fn recreate_swapchain_resources(
+        &mut self,
+        device: &Device,
+        render_pass: vk::RenderPass,
+        pipeline: vk::Pipeline,
+        swapchain: &Swapchain,
+    ) -> Result<()> {
+        self.destroy_swapchain_resources(device);
+
+        self.framebuffers = Self::create_framebuffers(device, render_pass, swapchain)?;
+        self.command_buffers = Self::create_command_buffers(
+            device,
+            self.command_pool,
+            render_pass,
+            pipeline,
+            self.vertex_buffer,
+            swapchain,
+            &self.framebuffers,
+        )?;
+        self.images_in_flight = swapchain.images.iter().map(|_| vk::Fence::null()).collect();
+
+        Ok(())
+    }
+
+    pub unsafe fn destroy(&mut self, device: &Device) {
+        device.destroy_semaphore(self.render_finished_semaphore, None);
+        device.destroy_semaphore(self.image_available_semaphore, None);
+        device.destroy_fence(self.in_flight_fence, None);
+
+        self.destroy_swapchain_resources(device);
+
+        device.destroy_buffer(self.vertex_buffer, None);
+        device.free_memory(self.vertex_buffer_memory, None);
+
+        device.destroy_command_pool(self.command_pool, None);
+    }
+}
@@ -3,10 +3,16 @@ use context::Context;
 use std::sync::Arc;
 use winit::window::Window;
 
+mod buffer;
 mod context;
+mod frame;
+mod pipeline;
+mod swapchain;
+mod vertex;
 
 pub struct Renderer {
     context: Context,
+    resized: bool,
 }
 
 impl Renderer {
@@ -15,6 +21,31 @@ impl Renderer {
         // SAFETY: Context::create is unsafe and requires caller to uphold Vulkan usage invariants
         let context = unsafe { Context::create(&window)? };
 
-        Ok(Self { context })
+        Ok(Self {
+            context,
+            resized: false,
+        })
+    }
+
+    /// Marks the swapchain as stale so the next `render` call rebuilds it
+    /// against the window's current size.
+    pub fn resize(&mut self) {
+        self.resized = true;
+    }
+
+    pub fn render(&mut self, window: &Window) -> Result<()> {
+        if self.resized {
+            let size = window.inner_size();
+            if size.width == 0 || size.height == 0 {
+                // Window is minimized; wait for a non-zero extent before recreating.
+                return Ok(());
+            }
+
+            unsafe { self.context.recreate_swapchain(window)? };
+            self.resized = false;
+            return Ok(());
+        }
+
+        unsafe { self.context.render(window) }
     }
 }
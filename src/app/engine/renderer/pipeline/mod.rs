@@ -0,0 +1,224 @@
+use std::sync::OnceLock;
+
+use anyhow::{Result, anyhow};
+use vulkanalia::bytecode::Bytecode;
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk;
+
+use super::vertex::Vertex;
+
+const VERTEX_SHADER_SOURCE: &str = include_str!("shaders/triangle.vert");
+const FRAGMENT_SHADER_SOURCE: &str = include_str!("shaders/triangle.frag");
+
+/// SPIR-V bytecode for the triangle shaders, compiled from GLSL source on
+/// first use and cached for the lifetime of the process.
+struct CompiledShaders {
+    vertex: Vec<u8>,
+    fragment: Vec<u8>,
+}
+
+static COMPILED_SHADERS: OnceLock<Result<CompiledShaders, String>> = OnceLock::new();
+
+/// The render pass and graphics pipeline for the triangle render path.
+/// Tied to the swapchain's format and extent, so both are recreated
+/// alongside the swapchain on resize.
+pub struct Pipeline {
+    pub render_pass: vk::RenderPass,
+    pub layout: vk::PipelineLayout,
+    pub pipeline: vk::Pipeline,
+}
+
+impl Pipeline {
+    pub unsafe fn create(
+        device: &Device,
+        swapchain_format: vk::Format,
+        swapchain_extent: vk::Extent2D,
+    ) -> Result<Self> {
+        let render_pass = Self::create_render_pass(device, swapchain_format)?;
+
+        let shaders = Self::compiled_shaders()?;
+        let vert_module = Self::create_shader_module(device, &shaders.vertex)?;
+        let frag_module = Self::create_shader_module(device, &shaders.fragment)?;
+
+        let vert_stage = vk::PipelineShaderStageCreateInfo::builder()
+            .stage(vk::ShaderStageFlags::VERTEX)
+            .module(vert_module)
+            .name(b"main\0");
+
+        let frag_stage = vk::PipelineShaderStageCreateInfo::builder()
+            .stage(vk::ShaderStageFlags::FRAGMENT)
+            .module(frag_module)
+            .name(b"main\0");
+
+        let binding_description = Vertex::binding_description();
+        let attribute_descriptions = Vertex::attribute_descriptions();
+        let bindings = &[binding_description];
+        let vertex_input_state = vk::PipelineVertexInputStateCreateInfo::builder()
+            .vertex_binding_descriptions(bindings)
+            .vertex_attribute_descriptions(&attribute_descriptions);
+
+        let input_assembly_state = vk::PipelineInputAssemblyStateCreateInfo::builder()
+            .topology(vk::PrimitiveTopology::TRIANGLE_LIST)
+            .primitive_restart_enable(false);
+
+        let viewport = vk::Viewport::builder()
+            .x(0.0)
+            .y(0.0)
+            .width(swapchain_extent.width as f32)
+            .height(swapchain_extent.height as f32)
+            .min_depth(0.0)
+            .max_depth(1.0);
+
+        let scissor = vk::Rect2D::builder()
+            .offset(vk::Offset2D { x: 0, y: 0 })
+            .extent(swapchain_extent);
+
+        let viewports = &[viewport];
+        let scissors = &[scissor];
+        let viewport_state = vk::PipelineViewportStateCreateInfo::builder()
+            .viewports(viewports)
+            .scissors(scissors);
+
+        let rasterization_state = vk::PipelineRasterizationStateCreateInfo::builder()
+            .depth_clamp_enable(false)
+            .rasterizer_discard_enable(false)
+            .polygon_mode(vk::PolygonMode::FILL)
+            .line_width(1.0)
+            .cull_mode(vk::CullModeFlags::BACK)
+            .front_face(vk::FrontFace::CLOCKWISE)
+            .depth_bias_enable(false);
+
+        let multisample_state = vk::PipelineMultisampleStateCreateInfo::builder()
+            .sample_shading_enable(false)
+            .rasterization_samples(vk::SampleCountFlags::_1);
+
+        let attachment = vk::PipelineColorBlendAttachmentState::builder()
+            .color_write_mask(vk::ColorComponentFlags::all())
+            .blend_enable(false);
+
+        let attachments = &[attachment];
+        let color_blend_state = vk::PipelineColorBlendStateCreateInfo::builder()
+            .logic_op_enable(false)
+            .attachments(attachments);
+
+        let layout_info = vk::PipelineLayoutCreateInfo::builder();
+        let layout = device.create_pipeline_layout(&layout_info, None)?;
+
+        let stages = &[vert_stage, frag_stage];
+        let info = vk::GraphicsPipelineCreateInfo::builder()
+            .stages(stages)
+            .vertex_input_state(&vertex_input_state)
+            .input_assembly_state(&input_assembly_state)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterization_state)
+            .multisample_state(&multisample_state)
+            .color_blend_state(&color_blend_state)
+            .layout(layout)
+            .render_pass(render_pass)
+            .subpass(0);
+
+        let pipeline = device
+            .create_graphics_pipelines(vk::PipelineCache::null(), &[info], None)?
+            .0[0];
+
+        device.destroy_shader_module(vert_module, None);
+        device.destroy_shader_module(frag_module, None);
+
+        Ok(Self {
+            render_pass,
+            layout,
+            pipeline,
+        })
+    }
+
+    unsafe fn create_render_pass(device: &Device, format: vk::Format) -> Result<vk::RenderPass> {
+        let color_attachment = vk::AttachmentDescription::builder()
+            .format(format)
+            .samples(vk::SampleCountFlags::_1)
+            .load_op(vk::AttachmentLoadOp::CLEAR)
+            .store_op(vk::AttachmentStoreOp::STORE)
+            .stencil_load_op(vk::AttachmentLoadOp::DONT_CARE)
+            .stencil_store_op(vk::AttachmentStoreOp::DONT_CARE)
+            .initial_layout(vk::ImageLayout::UNDEFINED)
+            .final_layout(vk::ImageLayout::PRESENT_SRC_KHR);
+
+        let color_attachment_ref = vk::AttachmentReference::builder()
+            .attachment(0)
+            .layout(vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL);
+
+        let color_attachments = &[color_attachment_ref];
+        let subpass = vk::SubpassDescription::builder()
+            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+            .color_attachments(color_attachments);
+
+        let dependency = vk::SubpassDependency::builder()
+            .src_subpass(vk::SUBPASS_EXTERNAL)
+            .dst_subpass(0)
+            .src_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+            .src_access_mask(vk::AccessFlags::empty())
+            .dst_stage_mask(vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT)
+            .dst_access_mask(vk::AccessFlags::COLOR_ATTACHMENT_WRITE);
+
+        let attachments = &[color_attachment];
+        let subpasses = &[subpass];
+        let dependencies = &[dependency];
+        let info = vk::RenderPassCreateInfo::builder()
+            .attachments(attachments)
+            .subpasses(subpasses)
+            .dependencies(dependencies);
+
+        Ok(device.create_render_pass(&info, None)?)
+    }
+
+    /// Compiles the vertex and fragment shader sources to SPIR-V the first
+    /// time this is called and returns the cached bytecode on every
+    /// subsequent call. `Pipeline::create` runs on every swapchain
+    /// recreation (e.g. on resize), but the shader bytecode never changes,
+    /// so only the render pass and pipeline object need to be rebuilt then.
+    fn compiled_shaders() -> Result<&'static CompiledShaders> {
+        let result = COMPILED_SHADERS.get_or_init(|| {
+            let compiler =
+                shaderc::Compiler::new().ok_or_else(|| "Failed to create shader compiler.".to_string())?;
+            let vertex = compiler
+                .compile_into_spirv(
+                    VERTEX_SHADER_SOURCE,
+                    shaderc::ShaderKind::Vertex,
+                    "triangle.vert",
+                    "main",
+                    None,
+                )
+                .map_err(|e| format!("Failed to compile triangle.vert: {e}"))?
+                .as_binary_u8()
+                .to_vec();
+            let fragment = compiler
+                .compile_into_spirv(
+                    FRAGMENT_SHADER_SOURCE,
+                    shaderc::ShaderKind::Fragment,
+                    "triangle.frag",
+                    "main",
+                    None,
+                )
+                .map_err(|e| format!("Failed to compile triangle.frag: {e}"))?
+                .as_binary_u8()
+                .to_vec();
+            Ok(CompiledShaders { vertex, fragment })
+        });
+
+        result.as_ref().map_err(|e| anyhow!(e.clone()))
+    }
+
+    unsafe fn create_shader_module(device: &Device, bytecode: &[u8]) -> Result<vk::ShaderModule> {
+        let bytecode = Bytecode::new(bytecode)?;
+        let info = vk::ShaderModuleCreateInfo::builder()
+            .code_size(bytecode.code_size())
+            .code(bytecode.code());
+
+        Ok(device.create_shader_module(&info, None)?)
+    }
+
+    pub unsafe fn destroy(&self, device: &Device) {
+        device.destroy_pipeline(self.pipeline, None);
+        device.destroy_pipeline_layout(self.layout, None);
+        device.destroy_render_pass(self.render_pass, None);
+    }
+}
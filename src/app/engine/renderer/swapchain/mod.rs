@@ -0,0 +1,168 @@
+use anyhow::Result;
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk;
+use vulkanalia::vk::{KhrSurfaceExtension, KhrSwapchainExtension};
+use winit::window::Window;
+
+/// Swapchain support details reported by a physical device for a given surface.
+#[derive(Clone, Debug)]
+pub struct SwapchainSupport {
+    pub capabilities: vk::SurfaceCapabilitiesKHR,
+    pub formats: Vec<vk::SurfaceFormatKHR>,
+    pub present_modes: Vec<vk::PresentModeKHR>,
+}
+
+impl SwapchainSupport {
+    pub unsafe fn get(
+        instance: &Instance,
+        surface: vk::SurfaceKHR,
+        physical_device: vk::PhysicalDevice,
+    ) -> Result<Self> {
+        Ok(Self {
+            capabilities: instance
+                .get_physical_device_surface_capabilities_khr(physical_device, surface)?,
+            formats: instance.get_physical_device_surface_formats_khr(physical_device, surface)?,
+            present_modes: instance
+                .get_physical_device_surface_present_modes_khr(physical_device, surface)?,
+        })
+    }
+}
+
+pub struct Swapchain {
+    pub swapchain: vk::SwapchainKHR,
+    pub format: vk::Format,
+    pub extent: vk::Extent2D,
+    pub images: Vec<vk::Image>,
+    pub image_views: Vec<vk::ImageView>,
+}
+
+impl Swapchain {
+    pub unsafe fn create(
+        window: &Window,
+        instance: &Instance,
+        device: &Device,
+        surface: vk::SurfaceKHR,
+        physical_device: vk::PhysicalDevice,
+        graphics_queue_family: u32,
+        present_queue_family: u32,
+    ) -> Result<Self> {
+        let support = SwapchainSupport::get(instance, surface, physical_device)?;
+
+        let surface_format = Self::pick_surface_format(&support.formats);
+        let present_mode = Self::pick_present_mode(&support.present_modes);
+        let extent = Self::pick_extent(window, support.capabilities);
+
+        let mut image_count = support.capabilities.min_image_count + 1;
+        if support.capabilities.max_image_count != 0
+            && image_count > support.capabilities.max_image_count
+        {
+            image_count = support.capabilities.max_image_count;
+        }
+
+        let mut queue_family_indices = vec![];
+        let image_sharing_mode = if graphics_queue_family != present_queue_family {
+            queue_family_indices.push(graphics_queue_family);
+            queue_family_indices.push(present_queue_family);
+            vk::SharingMode::CONCURRENT
+        } else {
+            vk::SharingMode::EXCLUSIVE
+        };
+
+        let info = vk::SwapchainCreateInfoKHR::builder()
+            .surface(surface)
+            .min_image_count(image_count)
+            .image_format(surface_format.format)
+            .image_color_space(surface_format.color_space)
+            .image_extent(extent)
+            .image_array_layers(1)
+            .image_usage(vk::ImageUsageFlags::COLOR_ATTACHMENT)
+            .image_sharing_mode(image_sharing_mode)
+            .queue_family_indices(&queue_family_indices)
+            .pre_transform(support.capabilities.current_transform)
+            .composite_alpha(vk::CompositeAlphaFlagsKHR::OPAQUE)
+            .present_mode(present_mode)
+            .clipped(true)
+            .old_swapchain(vk::SwapchainKHR::null());
+
+        let swapchain = device.create_swapchain_khr(&info, None)?;
+        let images = device.get_swapchain_images_khr(swapchain)?;
+        let image_views = images
+            .iter()
+            .map(|i| Self::create_image_view(device, *i, surface_format.format))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            swapchain,
+            format: surface_format.format,
+            extent,
+            images,
+            image_views,
+        })
+    }
+
+    unsafe fn create_image_view(
+        device: &Device,
+        image: vk::Image,
+        format: vk::Format,
+    ) -> Result<vk::ImageView> {
+        let subresource_range = vk::ImageSubresourceRange::builder()
+            .aspect_mask(vk::ImageAspectFlags::COLOR)
+            .base_mip_level(0)
+            .level_count(1)
+            .base_array_layer(0)
+            .layer_count(1);
+
+        let info = vk::ImageViewCreateInfo::builder()
+            .image(image)
+            .view_type(vk::ImageViewType::_2D)
+            .format(format)
+            .components(vk::ComponentMapping::default())
+            .subresource_range(subresource_range);
+
+        Ok(device.create_image_view(&info, None)?)
+    }
+
+    fn pick_surface_format(formats: &[vk::SurfaceFormatKHR]) -> vk::SurfaceFormatKHR {
+        formats
+            .iter()
+            .find(|f| {
+                f.format == vk::Format::B8G8R8A8_SRGB
+                    && f.color_space == vk::ColorSpaceKHR::SRGB_NONLINEAR
+            })
+            .copied()
+            .unwrap_or(formats[0])
+    }
+
+    fn pick_present_mode(present_modes: &[vk::PresentModeKHR]) -> vk::PresentModeKHR {
+        present_modes
+            .iter()
+            .copied()
+            .find(|m| *m == vk::PresentModeKHR::MAILBOX)
+            .unwrap_or(vk::PresentModeKHR::FIFO)
+    }
+
+    fn pick_extent(window: &Window, capabilities: vk::SurfaceCapabilitiesKHR) -> vk::Extent2D {
+        if capabilities.current_extent.width != u32::MAX {
+            capabilities.current_extent
+        } else {
+            let size = window.inner_size();
+            vk::Extent2D::builder()
+                .width(size.width.clamp(
+                    capabilities.min_image_extent.width,
+                    capabilities.max_image_extent.width,
+                ))
+                .height(size.height.clamp(
+                    capabilities.min_image_extent.height,
+                    capabilities.max_image_extent.height,
+                ))
+                .build()
+        }
+    }
+
+    pub unsafe fn destroy(&self, device: &Device) {
+        self.image_views
+            .iter()
+            .for_each(|view| device.destroy_image_view(*view, None));
+        device.destroy_swapchain_khr(self.swapchain, None);
+    }
+}
@@ -0,0 +1,48 @@
+use std::mem::size_of;
+use vulkanalia::prelude::v1_0::*;
+use vulkanalia::vk;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct Vertex {
+    pub pos: [f32; 2],
+    pub color: [f32; 3],
+}
+
+impl Vertex {
+    pub const fn new(pos: [f32; 2], color: [f32; 3]) -> Self {
+        Self { pos, color }
+    }
+
+    pub fn binding_description() -> vk::VertexInputBindingDescription {
+        vk::VertexInputBindingDescription::builder()
+            .binding(0)
+            .stride(size_of::<Vertex>() as u32)
+            .input_rate(vk::VertexInputRate::VERTEX)
+            .build()
+    }
+
+    pub fn attribute_descriptions() -> [vk::VertexInputAttributeDescription; 2] {
+        let pos = vk::VertexInputAttributeDescription::builder()
+            .binding(0)
+            .location(0)
+            .format(vk::Format::R32G32_SFLOAT)
+            .offset(0)
+            .build();
+
+        let color = vk::VertexInputAttributeDescription::builder()
+            .binding(0)
+            .location(1)
+            .format(vk::Format::R32G32B32_SFLOAT)
+            .offset(size_of::<[f32; 2]>() as u32)
+            .build();
+
+        [pos, color]
+    }
+}
+
+pub const VERTICES: [Vertex; 3] = [
+    Vertex::new([0.0, -0.5], [1.0, 0.0, 0.0]),
+    Vertex::new([0.5, 0.5], [0.0, 1.0, 0.0]),
+    Vertex::new([-0.5, 0.5], [0.0, 0.0, 1.0]),
+];
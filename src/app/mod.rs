@@ -31,13 +31,8 @@ impl ApplicationHandler for App {
         window_id: WindowId,
         event: WindowEvent,
     ) {
-        match event {
-            WindowEvent::CloseRequested => {
-                // tell the event loop to exit cleanly
-                event_loop.exit();
-            }
-            // TODO: handle resize / input / redraw
-            _ => {}
+        if let Some(engine) = self.engine.as_mut() {
+            engine.window_event(event_loop, window_id, event);
         }
     }
 